@@ -0,0 +1,46 @@
+use crate::formats::YUVSource;
+
+/// Describes how chroma samples are subsampled and laid out relative to luma.
+///
+/// [`YUVSource`] implementations only emit the decoder's native planar 4:2:0; this descriptor lets the
+/// `formats::yuv2rgb` converters also accept other layouts (NV12/NV21 semi-planar, 4:2:2, 4:4:4) from
+/// sources beyond the decoder.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ChromaFormat {
+    /// 4:2:0, planar U and V, each subsampled 2x horizontally and vertically. The decoder's native format.
+    Yuv420Planar,
+    /// 4:2:0, semi-planar with U and V interleaved into a single plane. `swap_uv` selects NV21 (V before
+    /// U) instead of NV12 (U before V) ordering.
+    Nv12 { swap_uv: bool },
+    /// 4:2:2, planar U and V subsampled 2x horizontally only.
+    Yuv422Planar,
+    /// 4:4:4, planar U and V with no subsampling.
+    Yuv444Planar,
+}
+
+impl ChromaFormat {
+    /// Returns the `(horizontal, vertical)` chroma subsampling factors for this format, i.e. how many
+    /// luma samples share one chroma sample in each direction.
+    pub fn subsampling(self) -> (usize, usize) {
+        match self {
+            ChromaFormat::Yuv420Planar | ChromaFormat::Nv12 { .. } => (2, 2),
+            ChromaFormat::Yuv422Planar => (2, 1),
+            ChromaFormat::Yuv444Planar => (1, 1),
+        }
+    }
+}
+
+/// Extends [`YUVSource`] with a [`ChromaFormat`] descriptor.
+///
+/// Blanket-implemented for every `YUVSource`, so every source reports [`ChromaFormat::Yuv420Planar`]
+/// (the decoder's native output). Because of the blanket impl, a type cannot implement this trait
+/// itself, so [`Self::chroma_format`] cannot be overridden per source; other layouts are passed to the
+/// `*_with_chroma` converters as an explicit [`ChromaFormat`].
+pub trait ChromaSource: YUVSource {
+    /// Returns the chroma subsampling/layout of this source.
+    fn chroma_format(&self) -> ChromaFormat {
+        ChromaFormat::Yuv420Planar
+    }
+}
+
+impl<T: YUVSource> ChromaSource for T {}
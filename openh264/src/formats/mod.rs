@@ -0,0 +1,5 @@
+pub mod chroma;
+pub mod palette;
+pub mod rgb2yuv;
+pub mod y4m;
+pub mod yuv2rgb;
@@ -0,0 +1,186 @@
+use crate::formats::yuv2rgb::{write_rgb8_scalar, ColorConversion};
+use crate::formats::YUVSource;
+
+/// An RGB8 image reduced to an indexed palette of at most 256 colors.
+pub struct IndexedImage {
+    /// `(width, height)` of the image in pixels.
+    pub dim: (usize, usize),
+    /// The quantized color palette, at most 256 entries.
+    pub palette: Vec<[u8; 3]>,
+    /// One palette index per pixel, row-major.
+    pub indices: Vec<u8>,
+}
+
+/// Converts a decoded frame to an [`IndexedImage`] using median-cut palette quantization.
+///
+/// `max_colors` is clamped to `1..=256`. Reuses [`write_rgb8_scalar`] to get RGB8 first, then
+/// quantizes with [`quantize_rgb8`].
+pub fn quantize(source: &impl YUVSource, max_colors: usize) -> IndexedImage {
+    let dim = source.dimensions();
+    let mut rgb = vec![0u8; dim.0 * dim.1 * 3];
+    write_rgb8_scalar(
+        source.y(),
+        source.u(),
+        source.v(),
+        dim,
+        source.strides(),
+        ColorConversion::default(),
+        &mut rgb,
+    );
+
+    quantize_rgb8(&rgb, dim, max_colors)
+}
+
+/// Quantizes an interleaved RGB8 buffer to an [`IndexedImage`] using median-cut.
+///
+/// Starts with one box holding every pixel, then repeatedly splits the box whose widest channel
+/// (largest max-min spread) has the largest spread: sorts that box's pixels along that channel and
+/// splits at the median index. Recurses until `max_colors` boxes exist or no box can be split further.
+/// Each final box's palette entry is the per-channel average of its pixels; every pixel is then mapped
+/// to its nearest palette entry by squared RGB distance.
+pub fn quantize_rgb8(rgb: &[u8], dim: (usize, usize), max_colors: usize) -> IndexedImage {
+    let max_colors = max_colors.clamp(1, 256);
+
+    let pixels: Vec<[u8; 3]> = rgb.chunks_exact(3).map(|p| [p[0], p[1], p[2]]).collect();
+
+    if pixels.is_empty() {
+        return IndexedImage {
+            dim,
+            palette: Vec::new(),
+            indices: Vec::new(),
+        };
+    }
+
+    let mut boxes = vec![ColorBox {
+        indices: (0..pixels.len()).collect(),
+    }];
+
+    while boxes.len() < max_colors {
+        let Some((split_at, (channel, spread))) = boxes
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| b.indices.len() > 1)
+            .map(|(i, b)| (i, b.widest_channel(&pixels)))
+            .max_by_key(|&(_, (_, spread))| spread)
+        else {
+            break;
+        };
+
+        if spread == 0 {
+            break;
+        }
+
+        let target = boxes.swap_remove(split_at);
+        let mut indices = target.indices;
+        indices.sort_unstable_by_key(|&i| pixels[i][channel]);
+
+        let mid = indices.len() / 2;
+        let hi = indices.split_off(mid);
+
+        boxes.push(ColorBox { indices });
+        boxes.push(ColorBox { indices: hi });
+    }
+
+    let palette: Vec<[u8; 3]> = boxes.iter().map(|b| b.average(&pixels)).collect();
+    let indices = pixels.iter().map(|p| nearest_palette_index(p, &palette)).collect();
+
+    IndexedImage { dim, palette, indices }
+}
+
+/// A box of pixel indices (into the flat pixel buffer) considered together during median-cut.
+struct ColorBox {
+    indices: Vec<usize>,
+}
+
+impl ColorBox {
+    /// Returns the `(channel, spread)` of this box's widest RGB channel, where channel `0..3` is
+    /// R/G/B and spread is `max - min` over the box's pixels on that channel.
+    fn widest_channel(&self, pixels: &[[u8; 3]]) -> (usize, u8) {
+        (0..3)
+            .map(|channel| {
+                let (mut min, mut max) = (255u8, 0u8);
+                for &i in &self.indices {
+                    let v = pixels[i][channel];
+                    min = min.min(v);
+                    max = max.max(v);
+                }
+                (channel, max - min)
+            })
+            .max_by_key(|&(_, spread)| spread)
+            .unwrap()
+    }
+
+    /// Returns the per-channel average color of this box's pixels.
+    fn average(&self, pixels: &[[u8; 3]]) -> [u8; 3] {
+        let mut sum = [0u64; 3];
+        for &i in &self.indices {
+            for (channel, sum_channel) in sum.iter_mut().enumerate() {
+                *sum_channel += u64::from(pixels[i][channel]);
+            }
+        }
+
+        let n = self.indices.len() as u64;
+        [(sum[0] / n) as u8, (sum[1] / n) as u8, (sum[2] / n) as u8]
+    }
+}
+
+/// Returns the index of the palette entry nearest `pixel` by squared RGB distance.
+fn nearest_palette_index(pixel: &[u8; 3], palette: &[[u8; 3]]) -> u8 {
+    palette
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, color)| squared_distance(pixel, color))
+        .map(|(i, _)| i as u8)
+        .unwrap()
+}
+
+/// Squared Euclidean distance between two RGB8 colors.
+fn squared_distance(a: &[u8; 3], b: &[u8; 3]) -> u32 {
+    (0..3)
+        .map(|channel| {
+            let d = i32::from(a[channel]) - i32::from(b[channel]);
+            (d * d) as u32
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::quantize_rgb8;
+
+    #[test]
+    fn quantize_rgb8_respects_max_colors() {
+        let dim = (16, 16);
+        let mut rgb = vec![0u8; dim.0 * dim.1 * 3];
+        for (i, pixel) in rgb.chunks_exact_mut(3).enumerate() {
+            pixel[0] = (i * 7) as u8;
+            pixel[1] = (i * 13) as u8;
+            pixel[2] = (i * 29) as u8;
+        }
+
+        let image = quantize_rgb8(&rgb, dim, 8);
+
+        assert!(image.palette.len() <= 8);
+        assert_eq!(image.indices.len(), dim.0 * dim.1);
+        assert!(image.indices.iter().all(|&i| (i as usize) < image.palette.len()));
+    }
+
+    #[test]
+    fn quantize_rgb8_empty_input_yields_empty_palette() {
+        let image = quantize_rgb8(&[], (0, 0), 16);
+
+        assert!(image.palette.is_empty());
+        assert!(image.indices.is_empty());
+    }
+
+    #[test]
+    fn quantize_rgb8_single_color_yields_one_palette_entry() {
+        let dim = (4, 4);
+        let rgb = vec![42u8; dim.0 * dim.1 * 3];
+
+        let image = quantize_rgb8(&rgb, dim, 16);
+
+        assert_eq!(image.palette, vec![[42, 42, 42]]);
+        assert!(image.indices.iter().all(|&i| i == 0));
+    }
+}
@@ -0,0 +1,243 @@
+/// A source of interleaved RGB8 pixel data that can be converted into planar YUV420.
+///
+/// This is the encode-side counterpart to [`YUVSource`](crate::formats::YUVSource): implement it for
+/// whatever buffer layout you already have and feed the converted planes to
+/// [`Encoder`](crate::encoder::Encoder).
+pub trait RgbSource {
+    /// Returns the interleaved RGB8 pixel data, one `[r, g, b]` triplet per pixel, row-major, tightly
+    /// packed (no row padding).
+    fn rgb8(&self) -> &[u8];
+
+    /// Returns the `(width, height)` of this image in pixels.
+    ///
+    /// Width and height must both be even, and for the SIMD converter, width must be a multiple of 8.
+    fn dimensions(&self) -> (usize, usize);
+}
+
+/// Write planar YUV420 data from an [`RgbSource`] using scalar (non SIMD) math.
+pub fn write_yuv420_scalar(
+    source: &impl RgbSource,
+    strides: (usize, usize, usize),
+    y_plane: &mut [u8],
+    u_plane: &mut [u8],
+    v_plane: &mut [u8],
+) {
+    let rgb = source.rgb8();
+    let (width, height) = source.dimensions();
+    let rgb_bytes_per_row = 3 * width;
+
+    for y in 0..height {
+        for x in 0..width {
+            let base_rgb = y * rgb_bytes_per_row + x * 3;
+            let rgb_pixel = &rgb[base_rgb..base_rgb + 3];
+
+            let r = f32::from(rgb_pixel[0]);
+            let g = f32::from(rgb_pixel[1]);
+            let b = f32::from(rgb_pixel[2]);
+
+            let base_y = y * strides.0 + x;
+            y_plane[base_y] = 0.114f32.mul_add(b, 0.587f32.mul_add(g, 0.299f32 * r)) as u8;
+        }
+    }
+
+    for y in (0..height).step_by(2) {
+        for x in (0..width).step_by(2) {
+            let (r, g, b) = average_rgb_2x2(rgb, rgb_bytes_per_row, x, y);
+
+            let base_u = (y / 2) * strides.1 + (x / 2);
+            let base_v = (y / 2) * strides.2 + (x / 2);
+
+            u_plane[base_u] = (-0.169f32 * r - 0.331f32 * g + 0.5f32 * b + 128.0) as u8;
+            v_plane[base_v] = (0.5f32 * r - 0.419f32 * g - 0.081f32 * b + 128.0) as u8;
+        }
+    }
+}
+
+/// Averages the RGB values of the 2x2 block with top-left corner at `(x, y)`.
+fn average_rgb_2x2(rgb: &[u8], rgb_bytes_per_row: usize, x: usize, y: usize) -> (f32, f32, f32) {
+    let mut sum = [0u32; 3];
+
+    for dy in 0..2 {
+        for dx in 0..2 {
+            let base = (y + dy) * rgb_bytes_per_row + (x + dx) * 3;
+            sum[0] += u32::from(rgb[base]);
+            sum[1] += u32::from(rgb[base + 1]);
+            sum[2] += u32::from(rgb[base + 2]);
+        }
+    }
+
+    (sum[0] as f32 / 4.0, sum[1] as f32 / 4.0, sum[2] as f32 / 4.0)
+}
+
+/// Write planar YUV420 data from an [`RgbSource`] using f32x8 SIMD for the Y plane; the 2x2-averaged
+/// U and V planes are computed with scalar math.
+#[allow(clippy::identity_op)]
+pub fn write_yuv420_f32x8(
+    source: &impl RgbSource,
+    strides: (usize, usize, usize),
+    y_plane: &mut [u8],
+    u_plane: &mut [u8],
+    v_plane: &mut [u8],
+) {
+    let rgb = source.rgb8();
+    let (width, height) = source.dimensions();
+
+    assert_eq!(width % 8, 0);
+
+    let rgb_bytes_per_row: usize = 3 * width;
+
+    for y in 0..(height / 2) {
+        let base_rgb0 = 2 * y * rgb_bytes_per_row;
+        let top_row = &rgb[base_rgb0..base_rgb0 + rgb_bytes_per_row];
+        let base_rgb1 = (2 * y + 1) * rgb_bytes_per_row;
+        let bot_row = &rgb[base_rgb1..base_rgb1 + rgb_bytes_per_row];
+
+        let base_y0 = 2 * y * strides.0;
+        write_y_row_f32x8(top_row, width, &mut y_plane[base_y0..base_y0 + strides.0]);
+
+        let base_y1 = (2 * y + 1) * strides.0;
+        write_y_row_f32x8(bot_row, width, &mut y_plane[base_y1..base_y1 + strides.0]);
+
+        let base_u = y * strides.1;
+        let base_v = y * strides.2;
+        write_uv_row_scalar(
+            top_row,
+            bot_row,
+            width,
+            &mut u_plane[base_u..base_u + strides.1],
+            &mut v_plane[base_v..base_v + strides.2],
+        );
+    }
+}
+
+/// Write one row of Y values from an interleaved RGB8 row of `width` pixels using f32x8 SIMD.
+///
+/// `y_row` may be longer than `width` (e.g. a padded plane stride); only the first `width` bytes are
+/// written. `rgb_row` holds exactly `width` tightly-packed pixels, per [`RgbSource::rgb8`].
+#[allow(clippy::inline_always)]
+#[inline(always)]
+fn write_y_row_f32x8(rgb_row: &[u8], width: usize, y_row: &mut [u8]) {
+    const STEP: usize = 8;
+
+    let ry_mul = wide::f32x8::splat(0.299);
+    let gy_mul = wide::f32x8::splat(0.587);
+    let by_mul = wide::f32x8::splat(0.114);
+
+    assert_eq!(width % STEP, 0);
+
+    let mut base_rgb = 0;
+    let mut base_y = 0;
+
+    for _ in (0..width).step_by(STEP) {
+        let (r_pack, g_pack, b_pack) = load_rgb_f32x8(rgb_row, base_rgb);
+
+        let y_pack = b_pack.mul_add(by_mul, g_pack.mul_add(gy_mul, r_pack * ry_mul));
+        let y_arr = y_pack.fast_trunc_int().as_array_ref();
+
+        for (i, v) in y_arr.iter().enumerate() {
+            y_row[base_y + i] = *v as u8;
+        }
+
+        base_rgb += STEP * 3;
+        base_y += STEP;
+    }
+}
+
+/// Load 8 interleaved RGB8 pixels starting at `base` into separate R/G/B f32x8 lanes.
+#[allow(clippy::inline_always)]
+#[inline(always)]
+fn load_rgb_f32x8(rgb_row: &[u8], base: usize) -> (wide::f32x8, wide::f32x8, wide::f32x8) {
+    let mut r = [0.0f32; 8];
+    let mut g = [0.0f32; 8];
+    let mut b = [0.0f32; 8];
+
+    for i in 0..8 {
+        let rgb_pixel = &rgb_row[base + i * 3..base + i * 3 + 3];
+        r[i] = f32::from(rgb_pixel[0]);
+        g[i] = f32::from(rgb_pixel[1]);
+        b[i] = f32::from(rgb_pixel[2]);
+    }
+
+    (wide::f32x8::from(r), wide::f32x8::from(g), wide::f32x8::from(b))
+}
+
+/// Write one row of averaged U/V chroma samples from two interleaved RGB8 rows, 2x2 block averaged.
+fn write_uv_row_scalar(top_row: &[u8], bot_row: &[u8], width: usize, u_row: &mut [u8], v_row: &mut [u8]) {
+    for (i, x) in (0..width).step_by(2).enumerate() {
+        let mut sum = [0u32; 3];
+
+        for (row, dx) in [(top_row, 0usize), (top_row, 1), (bot_row, 0), (bot_row, 1)] {
+            let base = (x + dx) * 3;
+            sum[0] += u32::from(row[base]);
+            sum[1] += u32::from(row[base + 1]);
+            sum[2] += u32::from(row[base + 2]);
+        }
+
+        let r = sum[0] as f32 / 4.0;
+        let g = sum[1] as f32 / 4.0;
+        let b = sum[2] as f32 / 4.0;
+
+        u_row[i] = (-0.169f32 * r - 0.331f32 * g + 0.5f32 * b + 128.0) as u8;
+        v_row[i] = (0.5f32 * r - 0.419f32 * g - 0.081f32 * b + 128.0) as u8;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::formats::rgb2yuv::{write_yuv420_f32x8, write_yuv420_scalar, RgbSource};
+
+    struct GradientRgb {
+        dim: (usize, usize),
+        rgb: Vec<u8>,
+    }
+
+    impl GradientRgb {
+        fn new(dim: (usize, usize)) -> Self {
+            let (width, height) = dim;
+            let mut rgb = vec![0u8; width * height * 3];
+
+            for y in 0..height {
+                for x in 0..width {
+                    let base = (y * width + x) * 3;
+                    rgb[base] = (x % 256) as u8;
+                    rgb[base + 1] = (y % 256) as u8;
+                    rgb[base + 2] = ((x + y) % 256) as u8;
+                }
+            }
+
+            Self { dim, rgb }
+        }
+    }
+
+    impl RgbSource for GradientRgb {
+        fn rgb8(&self) -> &[u8] {
+            &self.rgb
+        }
+
+        fn dimensions(&self) -> (usize, usize) {
+            self.dim
+        }
+    }
+
+    #[test]
+    fn write_yuv420_f32x8_matches_scalar() {
+        let dim = (32, 16);
+        // exercise a padded plane stride, not just the tightly-packed case
+        let strides = (dim.0 + 8, dim.0 / 2 + 4, dim.0 / 2 + 4);
+        let source = GradientRgb::new(dim);
+
+        let mut y_scalar = vec![0u8; dim.1 * strides.0];
+        let mut u_scalar = vec![0u8; (dim.1 / 2) * strides.1];
+        let mut v_scalar = vec![0u8; (dim.1 / 2) * strides.2];
+        write_yuv420_scalar(&source, strides, &mut y_scalar, &mut u_scalar, &mut v_scalar);
+
+        let mut y_simd = vec![0u8; dim.1 * strides.0];
+        let mut u_simd = vec![0u8; (dim.1 / 2) * strides.1];
+        let mut v_simd = vec![0u8; (dim.1 / 2) * strides.2];
+        write_yuv420_f32x8(&source, strides, &mut y_simd, &mut u_simd, &mut v_simd);
+
+        assert_eq!(y_scalar, y_simd);
+        assert_eq!(u_scalar, u_simd);
+        assert_eq!(v_scalar, v_simd);
+    }
+}
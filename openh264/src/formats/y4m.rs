@@ -0,0 +1,202 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+
+use crate::formats::YUVSource;
+
+/// Writes frames to a YUV4MPEG2 (`.y4m`) stream, the raw pipe-friendly format understood by `ffmpeg`,
+/// `mpv` and most other video tooling.
+///
+/// All frames written through a single [`Y4mWriter`] must share the same dimensions, given at
+/// construction time.
+pub struct Y4mWriter<W: Write> {
+    writer: W,
+    dim: (usize, usize),
+    framerate: (u32, u32),
+    header_written: bool,
+}
+
+impl<W: Write> Y4mWriter<W> {
+    /// Creates a writer for 4:2:0 planar frames of size `dim` (width, height) at `framerate`
+    /// (numerator, denominator frames per second).
+    pub fn new(writer: W, dim: (usize, usize), framerate: (u32, u32)) -> Self {
+        Self {
+            writer,
+            dim,
+            framerate,
+            header_written: false,
+        }
+    }
+
+    /// Writes a single frame from a decoded [`YUVSource`].
+    pub fn write_frame(&mut self, source: &impl YUVSource) -> io::Result<()> {
+        if !self.header_written {
+            writeln!(
+                self.writer,
+                "YUV4MPEG2 W{} H{} F{}:{} Ip A1:1 C420jpeg",
+                self.dim.0, self.dim.1, self.framerate.0, self.framerate.1
+            )?;
+            self.header_written = true;
+        }
+
+        writeln!(self.writer, "FRAME")?;
+
+        let strides = source.strides();
+        write_plane(&mut self.writer, source.y(), self.dim.0, self.dim.1, strides.0)?;
+        write_plane(&mut self.writer, source.u(), self.dim.0 / 2, self.dim.1 / 2, strides.1)?;
+        write_plane(&mut self.writer, source.v(), self.dim.0 / 2, self.dim.1 / 2, strides.2)?;
+
+        Ok(())
+    }
+}
+
+fn write_plane<W: Write>(writer: &mut W, plane: &[u8], width: usize, height: usize, stride: usize) -> io::Result<()> {
+    for row in 0..height {
+        let base = row * stride;
+        writer.write_all(&plane[base..base + width])?;
+    }
+
+    Ok(())
+}
+
+/// A single planar YUV420 frame read back from a [`Y4mReader`].
+///
+/// Implements [`YUVSource`], so it can be fed directly into [`Encoder`](crate::encoder::Encoder).
+pub struct Y4mFrame {
+    dim: (usize, usize),
+    y: Vec<u8>,
+    u: Vec<u8>,
+    v: Vec<u8>,
+}
+
+impl YUVSource for Y4mFrame {
+    fn dimensions(&self) -> (usize, usize) {
+        self.dim
+    }
+
+    fn strides(&self) -> (usize, usize, usize) {
+        (self.dim.0, self.dim.0 / 2, self.dim.0 / 2)
+    }
+
+    fn y(&self) -> &[u8] {
+        &self.y
+    }
+
+    fn u(&self) -> &[u8] {
+        &self.u
+    }
+
+    fn v(&self) -> &[u8] {
+        &self.v
+    }
+}
+
+/// Reads frames from a YUV4MPEG2 (`.y4m`) stream into planar [`Y4mFrame`]s.
+pub struct Y4mReader<R: Read> {
+    reader: BufReader<R>,
+    dim: (usize, usize),
+}
+
+impl<R: Read> Y4mReader<R> {
+    /// Parses the stream header and returns a reader positioned to read frames.
+    pub fn new(reader: R) -> io::Result<Self> {
+        let mut reader = BufReader::new(reader);
+
+        let mut header = String::new();
+        reader.read_line(&mut header)?;
+
+        let dim =
+            parse_header_dimensions(&header).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid YUV4MPEG2 header"))?;
+
+        Ok(Self { reader, dim })
+    }
+
+    /// Returns the `(width, height)` of frames in this stream.
+    pub fn dimensions(&self) -> (usize, usize) {
+        self.dim
+    }
+
+    /// Reads the next frame, or `None` once the stream is exhausted.
+    pub fn read_frame(&mut self) -> io::Result<Option<Y4mFrame>> {
+        let mut marker = String::new();
+        if self.reader.read_line(&mut marker)? == 0 {
+            return Ok(None);
+        }
+
+        if !marker.starts_with("FRAME") {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "expected FRAME marker"));
+        }
+
+        let (width, height) = self.dim;
+        let chroma_len = (width / 2) * (height / 2);
+
+        let mut y = vec![0u8; width * height];
+        let mut u = vec![0u8; chroma_len];
+        let mut v = vec![0u8; chroma_len];
+
+        self.reader.read_exact(&mut y)?;
+        self.reader.read_exact(&mut u)?;
+        self.reader.read_exact(&mut v)?;
+
+        Ok(Some(Y4mFrame { dim: self.dim, y, u, v }))
+    }
+}
+
+/// Parses the `W<width> H<height>` fields out of a YUV4MPEG2 header line.
+fn parse_header_dimensions(header: &str) -> Option<(usize, usize)> {
+    let mut width = None;
+    let mut height = None;
+
+    for field in header.trim().split(' ').skip(1) {
+        if let Some(w) = field.strip_prefix('W') {
+            width = w.parse().ok();
+        } else if let Some(h) = field.strip_prefix('H') {
+            height = h.parse().ok();
+        }
+    }
+
+    Some((width?, height?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Y4mReader, Y4mWriter};
+    use crate::decoder::{Decoder, DecoderConfig};
+    use crate::formats::YUVSource;
+    use crate::OpenH264API;
+
+    #[test]
+    fn y4m_round_trip_matches_decoded_frame() {
+        let source = include_bytes!("../../tests/data/single_512x512_cavlc.h264");
+
+        let api = OpenH264API::from_source();
+        let config = DecoderConfig::default();
+        let mut decoder = Decoder::with_api_config(api, config).unwrap();
+        let yuv = decoder.decode(&source[..]).unwrap().unwrap();
+
+        let mut stream = Vec::new();
+        let mut writer = Y4mWriter::new(&mut stream, yuv.dimensions(), (25, 1));
+        writer.write_frame(&yuv).unwrap();
+
+        let mut reader = Y4mReader::new(stream.as_slice()).unwrap();
+        assert_eq!(reader.dimensions(), yuv.dimensions());
+
+        let frame = reader.read_frame().unwrap().expect("one frame");
+
+        let (width, height) = yuv.dimensions();
+        let strides = yuv.strides();
+        assert_planes_equal(frame.y(), yuv.y(), width, height, strides.0);
+        assert_planes_equal(frame.u(), yuv.u(), width / 2, height / 2, strides.1);
+        assert_planes_equal(frame.v(), yuv.v(), width / 2, height / 2, strides.2);
+
+        assert!(reader.read_frame().unwrap().is_none());
+    }
+
+    /// Compares a tightly-packed `actual` plane (as produced by [`Y4mFrame`], stride == width) against
+    /// an `expected` plane that may carry stride padding (as decoded `YUVSource` planes do), row by row.
+    fn assert_planes_equal(actual: &[u8], expected: &[u8], width: usize, height: usize, expected_stride: usize) {
+        for row in 0..height {
+            let actual_row = &actual[row * width..row * width + width];
+            let expected_row = &expected[row * expected_stride..row * expected_stride + width];
+            assert_eq!(actual_row, expected_row, "row {row} differs");
+        }
+    }
+}
@@ -1,3 +1,61 @@
+use crate::formats::chroma::ChromaFormat;
+
+/// Which YCbCr color matrix to use when converting between YUV and RGB.
+///
+/// This should match the `matrix_coefficients` field of the stream's VUI parameters, where present.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum ColorMatrix {
+    /// ITU-R BT.601, traditionally used for standard-definition video.
+    #[default]
+    Bt601,
+    /// ITU-R BT.709, traditionally used for high-definition video.
+    Bt709,
+}
+
+/// Whether luma/chroma samples use the full `0..=255` range or the limited "studio swing" range.
+///
+/// This should match the `video_full_range_flag` field of the stream's VUI parameters, where present.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum ColorRange {
+    /// Luma in `16..=235`, chroma in `16..=240`.
+    Limited,
+    /// Luma and chroma in `0..=255`.
+    #[default]
+    Full,
+}
+
+/// Selects the color matrix and range used by the `write_rgb8_*` conversion functions.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub struct ColorConversion {
+    pub matrix: ColorMatrix,
+    pub range: ColorRange,
+}
+
+impl ColorConversion {
+    /// Returns the `(rv, gu, gv, bu)` multipliers for this conversion's matrix, such that
+    /// `R = Y + rv*V'`, `G = Y + gu*U' + gv*V'`, `B = Y + bu*U'`, where `U'`/`V'` are the
+    /// range-expanded, zero-centered chroma samples returned by [`Self::expand_range`].
+    fn matrix_coefficients(self) -> (f32, f32, f32, f32) {
+        match self.matrix {
+            ColorMatrix::Bt601 => (1.402, -0.344, -0.714, 1.772),
+            ColorMatrix::Bt709 => (1.5748, -0.1873, -0.4681, 1.8556),
+        }
+    }
+
+    /// Expands raw `y`/`u`/`v` samples according to this conversion's range, returning
+    /// `(y, u - 128, v - 128)` scaled so they are ready to feed into the color matrix.
+    fn expand_range(self, y: f32, u: f32, v: f32) -> (f32, f32, f32) {
+        match self.range {
+            ColorRange::Full => (y, u - 128.0, v - 128.0),
+            ColorRange::Limited => (
+                (y - 16.0) * (255.0 / 219.0),
+                (u - 128.0) * (255.0 / 224.0),
+                (v - 128.0) * (255.0 / 224.0),
+            ),
+        }
+    }
+}
+
 /// Converts 8 float values into a f32x8 SIMD lane, taking into account block size.
 ///
 /// If you have a (pixel buffer) slice of at least 8 f32 values like so `[012345678...]`, this function
@@ -27,8 +85,11 @@ pub fn write_rgb8_scalar(
     v_plane: &[u8],
     dim: (usize, usize),
     strides: (usize, usize, usize),
+    conversion: ColorConversion,
     target: &mut [u8],
 ) {
+    let (rv, gu, gv, bu) = conversion.matrix_coefficients();
+
     for y in 0..dim.1 {
         for x in 0..dim.0 {
             let base_tgt = (y * dim.0 + x) * 3;
@@ -38,13 +99,15 @@ pub fn write_rgb8_scalar(
 
             let rgb_pixel = &mut target[base_tgt..base_tgt + 3];
 
-            let y = f32::from(y_plane[base_y]);
-            let u = f32::from(u_plane[base_u]);
-            let v = f32::from(v_plane[base_v]);
+            let (y, u, v) = conversion.expand_range(
+                f32::from(y_plane[base_y]),
+                f32::from(u_plane[base_u]),
+                f32::from(v_plane[base_v]),
+            );
 
-            rgb_pixel[0] = 1.402f32.mul_add(v - 128.0, y) as u8;
-            rgb_pixel[1] = 0.714f32.mul_add(-(v - 128.0), 0.344f32.mul_add(-(u - 128.0), y)) as u8;
-            rgb_pixel[2] = 1.772f32.mul_add(u - 128.0, y) as u8;
+            rgb_pixel[0] = rv.mul_add(v, y) as u8;
+            rgb_pixel[1] = gv.mul_add(v, gu.mul_add(u, y)) as u8;
+            rgb_pixel[2] = bu.mul_add(u, y) as u8;
         }
     }
 }
@@ -57,6 +120,7 @@ pub fn write_rgb8_f32x8(
     v_plane: &[u8],
     dim: (usize, usize),
     strides: (usize, usize, usize),
+    conversion: ColorConversion,
     target: &mut [u8],
 ) {
     const RGB_PIXEL_LEN: usize = 3;
@@ -83,7 +147,7 @@ pub fn write_rgb8_f32x8(
         // calculate first RGB row
         let base_tgt = 2 * y * rgb_bytes_per_row;
         let row_target = &mut target[base_tgt..base_tgt + rgb_bytes_per_row];
-        write_rgb8_f32x8_row(y_row, u_row, v_row, width, row_target);
+        write_rgb8_f32x8_row(y_row, u_row, v_row, width, conversion, row_target);
 
         // load Y values for second row
         let base_y = (2 * y + 1) * strides.0;
@@ -92,7 +156,7 @@ pub fn write_rgb8_f32x8(
         // calculate second RGB row
         let base_tgt = (2 * y + 1) * rgb_bytes_per_row;
         let row_target = &mut target[base_tgt..(base_tgt + rgb_bytes_per_row)];
-        write_rgb8_f32x8_row(y_row, u_row, v_row, width, row_target);
+        write_rgb8_f32x8_row(y_row, u_row, v_row, width, conversion, row_target);
     }
 }
 
@@ -100,7 +164,14 @@ pub fn write_rgb8_f32x8(
 #[allow(clippy::inline_always)]
 #[allow(clippy::similar_names)]
 #[inline(always)]
-fn write_rgb8_f32x8_row(y_row: &[u8], u_row: &[u8], v_row: &[u8], width: usize, target: &mut [u8]) {
+fn write_rgb8_f32x8_row(
+    y_row: &[u8],
+    u_row: &[u8],
+    v_row: &[u8],
+    width: usize,
+    conversion: ColorConversion,
+    target: &mut [u8],
+) {
     const STEP: usize = 8;
     const UV_STEP: usize = STEP / 2;
     const TGT_STEP: usize = STEP * 3;
@@ -108,10 +179,19 @@ fn write_rgb8_f32x8_row(y_row: &[u8], u_row: &[u8], v_row: &[u8], width: usize,
     assert_eq!(y_row.len(), u_row.len() * 2);
     assert_eq!(y_row.len(), v_row.len() * 2);
 
-    let rv_mul = wide::f32x8::splat(1.402);
-    let gu_mul = wide::f32x8::splat(-0.344);
-    let gv_mul = wide::f32x8::splat(-0.714);
-    let bu_mul = wide::f32x8::splat(1.772);
+    let (rv, gu, gv, bu) = conversion.matrix_coefficients();
+    let rv_mul = wide::f32x8::splat(rv);
+    let gu_mul = wide::f32x8::splat(gu);
+    let gv_mul = wide::f32x8::splat(gv);
+    let bu_mul = wide::f32x8::splat(bu);
+
+    let (y_scale, y_offset, c_scale) = match conversion.range {
+        ColorRange::Full => (1.0, 0.0, 1.0),
+        ColorRange::Limited => (255.0 / 219.0, 16.0, 255.0 / 224.0),
+    };
+    let y_scale = wide::f32x8::splat(y_scale);
+    let y_offset = wide::f32x8::splat(y_offset);
+    let c_scale = wide::f32x8::splat(c_scale);
 
     let upper_bound = wide::f32x8::splat(255.0);
     let lower_bound = wide::f32x8::splat(0.0);
@@ -130,9 +210,9 @@ fn write_rgb8_f32x8_row(y_row: &[u8], u_row: &[u8], v_row: &[u8], width: usize,
     for _ in (0..width).step_by(STEP) {
         let pixels = &mut target[base_tgt..(base_tgt + TGT_STEP)];
 
-        let y_pack: wide::f32x8 = f32x8_from_slice_with_blocksize!(y_row[base_y..], 1);
-        let u_pack: wide::f32x8 = f32x8_from_slice_with_blocksize!(u_row[base_uv..], 2) - 128.0;
-        let v_pack: wide::f32x8 = f32x8_from_slice_with_blocksize!(v_row[base_uv..], 2) - 128.0;
+        let y_pack: wide::f32x8 = (f32x8_from_slice_with_blocksize!(y_row[base_y..], 1) - y_offset) * y_scale;
+        let u_pack: wide::f32x8 = (f32x8_from_slice_with_blocksize!(u_row[base_uv..], 2) - 128.0) * c_scale;
+        let v_pack: wide::f32x8 = (f32x8_from_slice_with_blocksize!(v_row[base_uv..], 2) - 128.0) * c_scale;
 
         let r_pack = v_pack.mul_add(rv_mul, y_pack);
         let g_pack = v_pack.mul_add(gv_mul, u_pack.mul_add(gu_mul, y_pack));
@@ -158,33 +238,500 @@ fn write_rgb8_f32x8_row(y_row: &[u8], u_row: &[u8], v_row: &[u8], width: usize,
     }
 }
 
+/// Converts 8 u8 values into an i32x8 SIMD lane, taking into account block size.
+///
+/// Mirrors [`f32x8_from_slice_with_blocksize`], but for the integer fixed-point path.
+macro_rules! i32x8_from_slice_with_blocksize {
+    ($buf:expr, $block_size:expr) => {{
+        wide::i32x8::from([
+            (i32::from($buf[0])),
+            (i32::from($buf[1 / $block_size])),
+            (i32::from($buf[2 / $block_size])),
+            (i32::from($buf[3 / $block_size])),
+            (i32::from($buf[4 / $block_size])),
+            (i32::from($buf[5 / $block_size])),
+            (i32::from($buf[6 / $block_size])),
+            (i32::from($buf[7 / $block_size])),
+        ])
+    }};
+}
+
+/// Write RGB8 data from YUV420 using Q8 fixed-point integer SIMD math.
+///
+/// This is faster than [`write_rgb8_f32x8`] since it avoids the `u8 -> f32 -> u8` round trip, at the
+/// cost of only supporting the BT.601 full-range matrix (the crate's historical default). Coefficients
+/// are the BT.601 `(rv, gu, gv, bu)` multipliers from [`ColorConversion::matrix_coefficients`], each
+/// scaled by 256 and rounded to the nearest integer.
+#[allow(clippy::identity_op)]
+pub fn write_rgb8_i32x8(
+    y_plane: &[u8],
+    u_plane: &[u8],
+    v_plane: &[u8],
+    dim: (usize, usize),
+    strides: (usize, usize, usize),
+    target: &mut [u8],
+) {
+    const RGB_PIXEL_LEN: usize = 3;
+
+    // this assumes we are decoding YUV420
+    assert_eq!(y_plane.len(), u_plane.len() * 4);
+    assert_eq!(y_plane.len(), v_plane.len() * 4);
+    assert_eq!(dim.0 % 8, 0);
+
+    let (width, height) = dim;
+    let rgb_bytes_per_row: usize = RGB_PIXEL_LEN * width;
+
+    for y in 0..(height / 2) {
+        let base_u = y * strides.1;
+        let u_row = &u_plane[base_u..base_u + strides.1];
+        let base_v = y * strides.2;
+        let v_row = &v_plane[base_v..base_v + strides.2];
+
+        let base_y = 2 * y * strides.0;
+        let y_row = &y_plane[base_y..base_y + strides.0];
+        let base_tgt = 2 * y * rgb_bytes_per_row;
+        let row_target = &mut target[base_tgt..base_tgt + rgb_bytes_per_row];
+        write_rgb8_i32x8_row(y_row, u_row, v_row, width, row_target);
+
+        let base_y = (2 * y + 1) * strides.0;
+        let y_row = &y_plane[base_y..base_y + strides.0];
+        let base_tgt = (2 * y + 1) * rgb_bytes_per_row;
+        let row_target = &mut target[base_tgt..(base_tgt + rgb_bytes_per_row)];
+        write_rgb8_i32x8_row(y_row, u_row, v_row, width, row_target);
+    }
+}
+
+/// Write a single RGB8 row from YUV420 row data using Q8 fixed-point integer SIMD.
+#[allow(clippy::inline_always)]
+#[allow(clippy::similar_names)]
+#[inline(always)]
+fn write_rgb8_i32x8_row(y_row: &[u8], u_row: &[u8], v_row: &[u8], width: usize, target: &mut [u8]) {
+    const STEP: usize = 8;
+    const UV_STEP: usize = STEP / 2;
+    const TGT_STEP: usize = STEP * 3;
+    const SHIFT: i32 = 8;
+
+    assert_eq!(y_row.len(), u_row.len() * 2);
+    assert_eq!(y_row.len(), v_row.len() * 2);
+
+    let rv_mul = wide::i32x8::splat(359);
+    let gu_mul = wide::i32x8::splat(-88);
+    let gv_mul = wide::i32x8::splat(-183);
+    let bu_mul = wide::i32x8::splat(454);
+
+    let upper_bound = wide::i32x8::splat(255);
+    let lower_bound = wide::i32x8::splat(0);
+    let chroma_offset = wide::i32x8::splat(128);
+
+    assert_eq!(y_row.len() % STEP, 0);
+    assert_eq!(u_row.len() % UV_STEP, 0);
+    assert_eq!(v_row.len() % UV_STEP, 0);
+    assert_eq!(target.len() % TGT_STEP, 0);
+
+    let mut base_y = 0;
+    let mut base_uv = 0;
+    let mut base_tgt = 0;
+
+    for _ in (0..width).step_by(STEP) {
+        let pixels = &mut target[base_tgt..(base_tgt + TGT_STEP)];
+
+        let y_pack: wide::i32x8 = i32x8_from_slice_with_blocksize!(y_row[base_y..], 1);
+        let u_pack: wide::i32x8 = i32x8_from_slice_with_blocksize!(u_row[base_uv..], 2) - chroma_offset;
+        let v_pack: wide::i32x8 = i32x8_from_slice_with_blocksize!(v_row[base_uv..], 2) - chroma_offset;
+
+        let r_pack = y_pack + ((v_pack * rv_mul) >> SHIFT);
+        let g_pack = y_pack + (((u_pack * gu_mul) + (v_pack * gv_mul)) >> SHIFT);
+        let b_pack = y_pack + ((u_pack * bu_mul) >> SHIFT);
+
+        let (r_pack, g_pack, b_pack) = (
+            r_pack.min(upper_bound).max(lower_bound),
+            g_pack.min(upper_bound).max(lower_bound),
+            b_pack.min(upper_bound).max(lower_bound),
+        );
+
+        let (r_pack, g_pack, b_pack) = (r_pack.as_array_ref(), g_pack.as_array_ref(), b_pack.as_array_ref());
+
+        for i in 0..STEP {
+            pixels[3 * i] = r_pack[i] as u8;
+            pixels[(3 * i) + 1] = g_pack[i] as u8;
+            pixels[(3 * i) + 2] = b_pack[i] as u8;
+        }
+
+        base_y += STEP;
+        base_uv += UV_STEP;
+        base_tgt += TGT_STEP;
+    }
+}
+
+/// Write RGB8 data from a YUV source using scalar (non SIMD) math, supporting chroma layouts beyond
+/// planar 4:2:0: NV12/NV21 semi-planar, and 4:2:2/4:4:4 planar (see [`ChromaFormat`]).
+///
+/// For [`ChromaFormat::Nv12`], pass the interleaved UV plane as `u_plane` and pass an empty slice as
+/// `v_plane`; `strides.1` is the interleaved plane's row stride in bytes (`2 * chroma_width`).
+pub fn write_rgb8_scalar_with_chroma(
+    y_plane: &[u8],
+    u_plane: &[u8],
+    v_plane: &[u8],
+    dim: (usize, usize),
+    strides: (usize, usize, usize),
+    chroma: ChromaFormat,
+    conversion: ColorConversion,
+    target: &mut [u8],
+) {
+    let (h_sub, v_sub) = chroma.subsampling();
+    let (rv, gu, gv, bu) = conversion.matrix_coefficients();
+
+    for y in 0..dim.1 {
+        for x in 0..dim.0 {
+            let base_tgt = (y * dim.0 + x) * 3;
+            let base_y = y * strides.0 + x;
+            let cx = x / h_sub;
+            let cy = y / v_sub;
+
+            let (u, v) = match chroma {
+                ChromaFormat::Nv12 { swap_uv } => {
+                    let base_uv = cy * strides.1 + cx * 2;
+                    if swap_uv {
+                        (u_plane[base_uv + 1], u_plane[base_uv])
+                    } else {
+                        (u_plane[base_uv], u_plane[base_uv + 1])
+                    }
+                }
+                ChromaFormat::Yuv420Planar | ChromaFormat::Yuv422Planar | ChromaFormat::Yuv444Planar => {
+                    let base_u = cy * strides.1 + cx;
+                    let base_v = cy * strides.2 + cx;
+                    (u_plane[base_u], v_plane[base_v])
+                }
+            };
+
+            let rgb_pixel = &mut target[base_tgt..base_tgt + 3];
+            let (y, u, v) = conversion.expand_range(f32::from(y_plane[base_y]), f32::from(u), f32::from(v));
+
+            rgb_pixel[0] = rv.mul_add(v, y) as u8;
+            rgb_pixel[1] = gv.mul_add(v, gu.mul_add(u, y)) as u8;
+            rgb_pixel[2] = bu.mul_add(u, y) as u8;
+        }
+    }
+}
+
+/// Write RGB8 data from a planar YUV source using f32x8 SIMD, generalizing [`write_rgb8_f32x8`] to
+/// 4:2:0/4:2:2/4:4:4 chroma subsampling via [`ChromaFormat::subsampling`].
+///
+/// NV12/NV21 are not supported here since their interleaved UV plane doesn't fit this row kernel's
+/// unit-stride gather; use [`write_rgb8_scalar_with_chroma`] for those.
+pub fn write_rgb8_f32x8_with_chroma(
+    y_plane: &[u8],
+    u_plane: &[u8],
+    v_plane: &[u8],
+    dim: (usize, usize),
+    strides: (usize, usize, usize),
+    chroma: ChromaFormat,
+    conversion: ColorConversion,
+    target: &mut [u8],
+) {
+    const RGB_PIXEL_LEN: usize = 3;
+
+    assert_eq!(dim.0 % 8, 0);
+    assert!(matches!(
+        chroma,
+        ChromaFormat::Yuv420Planar | ChromaFormat::Yuv422Planar | ChromaFormat::Yuv444Planar
+    ));
+
+    let (h_sub, v_sub) = chroma.subsampling();
+    let (width, height) = dim;
+    let rgb_bytes_per_row: usize = RGB_PIXEL_LEN * width;
+
+    for y in 0..(height / v_sub) {
+        let base_u = y * strides.1;
+        let u_row = &u_plane[base_u..base_u + strides.1];
+        let base_v = y * strides.2;
+        let v_row = &v_plane[base_v..base_v + strides.2];
+
+        for r in 0..v_sub {
+            let base_y = (v_sub * y + r) * strides.0;
+            let y_row = &y_plane[base_y..base_y + strides.0];
+
+            let base_tgt = (v_sub * y + r) * rgb_bytes_per_row;
+            let row_target = &mut target[base_tgt..base_tgt + rgb_bytes_per_row];
+
+            write_rgb8_f32x8_row_with_chroma(y_row, u_row, v_row, width, h_sub, conversion, row_target);
+        }
+    }
+}
+
+/// Write a single RGB8 row from YUV row data using f32x8 SIMD, with `h_sub` horizontal chroma
+/// subsampling (`1` for 4:4:4, `2` for 4:2:0/4:2:2).
+#[allow(clippy::inline_always)]
+#[allow(clippy::similar_names)]
+#[inline(always)]
+fn write_rgb8_f32x8_row_with_chroma(
+    y_row: &[u8],
+    u_row: &[u8],
+    v_row: &[u8],
+    width: usize,
+    h_sub: usize,
+    conversion: ColorConversion,
+    target: &mut [u8],
+) {
+    const STEP: usize = 8;
+    const TGT_STEP: usize = STEP * 3;
+    let uv_step = STEP / h_sub;
+
+    let (rv, gu, gv, bu) = conversion.matrix_coefficients();
+    let rv_mul = wide::f32x8::splat(rv);
+    let gu_mul = wide::f32x8::splat(gu);
+    let gv_mul = wide::f32x8::splat(gv);
+    let bu_mul = wide::f32x8::splat(bu);
+
+    let (y_scale, y_offset, c_scale) = match conversion.range {
+        ColorRange::Full => (1.0, 0.0, 1.0),
+        ColorRange::Limited => (255.0 / 219.0, 16.0, 255.0 / 224.0),
+    };
+    let y_scale = wide::f32x8::splat(y_scale);
+    let y_offset = wide::f32x8::splat(y_offset);
+    let c_scale = wide::f32x8::splat(c_scale);
+
+    let upper_bound = wide::f32x8::splat(255.0);
+    let lower_bound = wide::f32x8::splat(0.0);
+
+    assert_eq!(y_row.len() % STEP, 0);
+    assert_eq!(target.len() % TGT_STEP, 0);
+
+    let mut base_y = 0;
+    let mut base_uv = 0;
+    let mut base_tgt = 0;
+
+    for _ in (0..width).step_by(STEP) {
+        let pixels = &mut target[base_tgt..(base_tgt + TGT_STEP)];
+
+        let y_pack: wide::f32x8 = (f32x8_from_slice_with_blocksize!(y_row[base_y..], 1) - y_offset) * y_scale;
+        let u_pack: wide::f32x8 = (f32x8_from_slice_with_blocksize!(u_row[base_uv..], h_sub) - 128.0) * c_scale;
+        let v_pack: wide::f32x8 = (f32x8_from_slice_with_blocksize!(v_row[base_uv..], h_sub) - 128.0) * c_scale;
+
+        let r_pack = v_pack.mul_add(rv_mul, y_pack);
+        let g_pack = v_pack.mul_add(gv_mul, u_pack.mul_add(gu_mul, y_pack));
+        let b_pack = u_pack.mul_add(bu_mul, y_pack);
+
+        let (r_pack, g_pack, b_pack) = (
+            r_pack.fast_min(upper_bound).fast_max(lower_bound).fast_trunc_int(),
+            g_pack.fast_min(upper_bound).fast_max(lower_bound).fast_trunc_int(),
+            b_pack.fast_min(upper_bound).fast_max(lower_bound).fast_trunc_int(),
+        );
+
+        let (r_pack, g_pack, b_pack) = (r_pack.as_array_ref(), g_pack.as_array_ref(), b_pack.as_array_ref());
+
+        for i in 0..STEP {
+            pixels[3 * i] = r_pack[i] as u8;
+            pixels[(3 * i) + 1] = g_pack[i] as u8;
+            pixels[(3 * i) + 2] = b_pack[i] as u8;
+        }
+
+        base_y += STEP;
+        base_uv += uv_step;
+        base_tgt += TGT_STEP;
+    }
+}
+
+/// Write RGB8 data from YUV420, automatically picking the fastest conversion path the current CPU
+/// supports: the integer Q8 fixed-point path, then the `f32x8` path, falling back to scalar.
+///
+/// The integer path only implements the BT.601 full-range matrix, so it is only used when
+/// `conversion` selects that (the crate's historical default); other conversions use
+/// `write_rgb8_f32x8`. CPU detection is only done on `x86_64`: on other targets, and whenever the
+/// width is not a multiple of 8, the scalar path is used.
+pub fn write_rgb8_auto(
+    y_plane: &[u8],
+    u_plane: &[u8],
+    v_plane: &[u8],
+    dim: (usize, usize),
+    strides: (usize, usize, usize),
+    conversion: ColorConversion,
+    target: &mut [u8],
+) {
+    #[cfg(target_arch = "x86_64")]
+    {
+        let is_bt601_full = conversion.matrix == ColorMatrix::Bt601 && conversion.range == ColorRange::Full;
+
+        if is_bt601_full && dim.0 % 8 == 0 && is_x86_feature_detected!("avx2") {
+            return write_rgb8_i32x8(y_plane, u_plane, v_plane, dim, strides, target);
+        }
+
+        if dim.0 % 8 == 0 && is_x86_feature_detected!("sse2") {
+            return write_rgb8_f32x8(y_plane, u_plane, v_plane, dim, strides, conversion, target);
+        }
+    }
+
+    write_rgb8_scalar(y_plane, u_plane, v_plane, dim, strides, conversion, target);
+}
+
 #[cfg(test)]
 mod test {
     use crate::decoder::{Decoder, DecoderConfig};
-    use crate::formats::yuv2rgb::{write_rgb8_f32x8, write_rgb8_scalar};
+    use crate::formats::chroma::ChromaFormat;
+    use crate::formats::yuv2rgb::{
+        write_rgb8_f32x8, write_rgb8_f32x8_with_chroma, write_rgb8_i32x8, write_rgb8_scalar,
+        write_rgb8_scalar_with_chroma, ColorConversion, ColorMatrix, ColorRange,
+    };
     use crate::formats::YUVSource;
     use crate::OpenH264API;
 
-    #[test]
-    fn write_rgb8_f32x8_matches_scalar() {
+    fn decode_single_frame() -> impl YUVSource {
         let source = include_bytes!("../../tests/data/single_512x512_cavlc.h264");
 
         let api = OpenH264API::from_source();
         let config = DecoderConfig::default();
         let mut decoder = Decoder::with_api_config(api, config).unwrap();
 
-        let mut rgb = vec![0; 2000 * 2000 * 3];
-        let yuv = decoder.decode(&source[..]).unwrap().unwrap();
+        decoder.decode(&source[..]).unwrap().unwrap()
+    }
+
+    #[test]
+    fn write_rgb8_f32x8_matches_scalar() {
+        let yuv = decode_single_frame();
+        let dim = yuv.dimensions();
+        let rgb_len = dim.0 * dim.1 * 3;
+
+        let mut tgt = vec![0; rgb_len];
+        write_rgb8_scalar(
+            yuv.y(),
+            yuv.u(),
+            yuv.v(),
+            yuv.dimensions(),
+            yuv.strides(),
+            ColorConversion::default(),
+            &mut tgt,
+        );
+
+        let mut tgt2 = vec![0; rgb_len];
+        write_rgb8_f32x8(
+            yuv.y(),
+            yuv.u(),
+            yuv.v(),
+            yuv.dimensions(),
+            yuv.strides(),
+            ColorConversion::default(),
+            &mut tgt2,
+        );
+
+        assert_eq!(tgt, tgt2);
+    }
+
+    #[test]
+    fn write_rgb8_f32x8_matches_scalar_bt709_limited() {
+        let yuv = decode_single_frame();
+        let dim = yuv.dimensions();
+        let rgb_len = dim.0 * dim.1 * 3;
+        let conversion = ColorConversion {
+            matrix: ColorMatrix::Bt709,
+            range: ColorRange::Limited,
+        };
+
+        let mut tgt = vec![0; rgb_len];
+        write_rgb8_scalar(yuv.y(), yuv.u(), yuv.v(), yuv.dimensions(), yuv.strides(), conversion, &mut tgt);
+
+        let mut tgt2 = vec![0; rgb_len];
+        write_rgb8_f32x8(yuv.y(), yuv.u(), yuv.v(), yuv.dimensions(), yuv.strides(), conversion, &mut tgt2);
+
+        assert_eq!(tgt, tgt2);
+    }
+
+    #[test]
+    fn write_rgb8_i32x8_matches_scalar_within_one() {
+        let yuv = decode_single_frame();
         let dim = yuv.dimensions();
         let rgb_len = dim.0 * dim.1 * 3;
 
-        let tgt = &mut rgb[0..rgb_len];
+        let mut tgt = vec![0; rgb_len];
+        write_rgb8_scalar(
+            yuv.y(),
+            yuv.u(),
+            yuv.v(),
+            yuv.dimensions(),
+            yuv.strides(),
+            ColorConversion::default(),
+            &mut tgt,
+        );
+
+        let mut tgt2 = vec![0; rgb_len];
+        write_rgb8_i32x8(yuv.y(), yuv.u(), yuv.v(), yuv.dimensions(), yuv.strides(), &mut tgt2);
+
+        for (a, b) in tgt.iter().zip(tgt2.iter()) {
+            assert!((i16::from(*a) - i16::from(*b)).abs() <= 1);
+        }
+    }
+
+    #[test]
+    fn write_rgb8_f32x8_with_chroma_matches_scalar_420() {
+        let yuv = decode_single_frame();
+        let dim = yuv.dimensions();
+        let rgb_len = dim.0 * dim.1 * 3;
 
-        write_rgb8_scalar(yuv.y(), yuv.u(), yuv.v(), yuv.dimensions(), yuv.strides(), tgt);
+        let mut tgt = vec![0; rgb_len];
+        write_rgb8_scalar_with_chroma(
+            yuv.y(),
+            yuv.u(),
+            yuv.v(),
+            yuv.dimensions(),
+            yuv.strides(),
+            ChromaFormat::Yuv420Planar,
+            ColorConversion::default(),
+            &mut tgt,
+        );
 
-        let mut tgt2 = vec![0; tgt.len()];
-        write_rgb8_f32x8(yuv.y(), yuv.u(), yuv.v(), yuv.dimensions(), yuv.strides(), &mut tgt2);
+        let mut tgt2 = vec![0; rgb_len];
+        write_rgb8_f32x8_with_chroma(
+            yuv.y(),
+            yuv.u(),
+            yuv.v(),
+            yuv.dimensions(),
+            yuv.strides(),
+            ChromaFormat::Yuv420Planar,
+            ColorConversion::default(),
+            &mut tgt2,
+        );
 
         assert_eq!(tgt, tgt2);
     }
+
+    #[test]
+    fn write_rgb8_scalar_with_chroma_nv12_matches_planar() {
+        let yuv = decode_single_frame();
+        let dim = yuv.dimensions();
+        let rgb_len = dim.0 * dim.1 * 3;
+        let (chroma_width, chroma_height) = (dim.0 / 2, dim.1 / 2);
+
+        let mut nv12_uv = vec![0u8; chroma_width * chroma_height * 2];
+        for cy in 0..chroma_height {
+            for cx in 0..chroma_width {
+                nv12_uv[(cy * chroma_width + cx) * 2] = yuv.u()[cy * yuv.strides().1 + cx];
+                nv12_uv[(cy * chroma_width + cx) * 2 + 1] = yuv.v()[cy * yuv.strides().2 + cx];
+            }
+        }
+
+        let mut planar_tgt = vec![0; rgb_len];
+        write_rgb8_scalar_with_chroma(
+            yuv.y(),
+            yuv.u(),
+            yuv.v(),
+            yuv.dimensions(),
+            yuv.strides(),
+            ChromaFormat::Yuv420Planar,
+            ColorConversion::default(),
+            &mut planar_tgt,
+        );
+
+        let mut nv12_tgt = vec![0; rgb_len];
+        write_rgb8_scalar_with_chroma(
+            yuv.y(),
+            &nv12_uv,
+            &[],
+            yuv.dimensions(),
+            (yuv.strides().0, chroma_width * 2, 0),
+            ChromaFormat::Nv12 { swap_uv: false },
+            ColorConversion::default(),
+            &mut nv12_tgt,
+        );
+
+        assert_eq!(planar_tgt, nv12_tgt);
+    }
 }